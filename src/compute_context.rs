@@ -0,0 +1,185 @@
+// src/compute_context.rs
+//
+// `introduction` and `parallel_sums_64` each hand-roll the same
+// instance -> adapter -> device -> buffer -> bind-group -> dispatch -> map sequence.
+// `ComputeContext` collapses that into `upload` / `bind` / `dispatch` / `read_back`, and
+// `TypedBuffer<T>` tracks a buffer's element type, count and usage so it can't be bound
+// where a different element type is expected.
+
+use std::error::Error;
+use std::marker::PhantomData;
+
+use bytemuck::{cast_slice, Pod, Zeroable};
+use futures_intrusive::channel::shared::oneshot_channel;
+use wgpu::util::DeviceExt;
+
+use crate::gpu_api;
+
+/// A GPU buffer that remembers its element type, element count and usage flags.
+pub struct TypedBuffer<T: Pod + Zeroable> {
+    buffer: gpu_api::Buffer,
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Pod + Zeroable> TypedBuffer<T> {
+    fn byte_size(&self) -> u64 {
+        (self.len * std::mem::size_of::<T>()) as u64
+    }
+}
+
+/// Anything `ComputeContext::bind` can place in a bind group entry.
+pub trait Binding {
+    fn binding_resource(&self) -> wgpu::BindingResource<'_>;
+}
+
+impl<T: Pod + Zeroable> Binding for TypedBuffer<T> {
+    fn binding_resource(&self) -> wgpu::BindingResource<'_> {
+        self.buffer.as_entire_binding()
+    }
+}
+
+/// Holds the device/queue and a cached compute pipeline for one shader entry point.
+pub struct ComputeContext {
+    device: gpu_api::Device,
+    queue: gpu_api::Queue,
+    pipeline: gpu_api::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+}
+
+impl ComputeContext {
+    /// Build a context around a compute shader with a single bind group, whose layout is
+    /// `layout_entries` (binding indices must match the order buffers are passed to `bind`).
+    pub fn new(
+        device: gpu_api::Device,
+        queue: gpu_api::Queue,
+        module_source: wgpu::ShaderModuleDescriptor<'_>,
+        layout_entries: &[wgpu::BindGroupLayoutEntry],
+        entry_point: &str,
+    ) -> Self {
+        let module = device.create_shader_module(module_source);
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("compute-context-bgl"),
+            entries: layout_entries,
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("compute-context-pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("compute-context-pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &module,
+            entry_point,
+        });
+
+        Self { device, queue, pipeline, bind_group_layout }
+    }
+
+    /// The device backing this context, e.g. for callers that need to build extra
+    /// buffers or passes `ComputeContext` doesn't cover (indirect dispatch args, ...).
+    pub fn device(&self) -> &gpu_api::Device {
+        &self.device
+    }
+
+    /// The queue backing this context.
+    pub fn queue(&self) -> &gpu_api::Queue {
+        &self.queue
+    }
+
+    /// The cached compute pipeline, e.g. for callers recording their own compute pass
+    /// against it (indirect dispatch).
+    pub fn pipeline(&self) -> &gpu_api::ComputePipeline {
+        &self.pipeline
+    }
+
+    /// Upload `data` into a freshly created buffer usable as a storage binding and as a
+    /// copy source (for `read_back`).
+    pub fn upload<T: Pod + Zeroable>(&self, data: &[T]) -> TypedBuffer<T> {
+        let buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("typed-buffer-upload"),
+            contents: cast_slice(data),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        });
+        TypedBuffer { buffer, len: data.len(), _marker: PhantomData }
+    }
+
+    /// Allocate an empty `len`-element output buffer, usable as a storage binding and as
+    /// a copy source (for `read_back`).
+    pub fn allocate<T: Pod + Zeroable>(&self, len: usize) -> TypedBuffer<T> {
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("typed-buffer-allocate"),
+            size: (len * std::mem::size_of::<T>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        TypedBuffer { buffer, len, _marker: PhantomData }
+    }
+
+    /// Build a bind group against the cached pipeline's layout, binding index `i` to
+    /// `bindings[i]`.
+    pub fn bind(&self, bindings: &[&dyn Binding]) -> wgpu::BindGroup {
+        let entries: Vec<wgpu::BindGroupEntry> = bindings
+            .iter()
+            .enumerate()
+            .map(|(i, b)| wgpu::BindGroupEntry { binding: i as u32, resource: b.binding_resource() })
+            .collect();
+        self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("compute-context-bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &entries,
+        })
+    }
+
+    /// Encode, submit and wait on a single compute pass against `bind_group`.
+    pub fn dispatch(&self, bind_group: &wgpu::BindGroup, workgroups: (u32, u32, u32)) {
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("compute-context-encoder"),
+        });
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("compute-context-pass"),
+            });
+            cpass.set_pipeline(&self.pipeline);
+            cpass.set_bind_group(0, bind_group, &[]);
+            cpass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+        }
+        self.queue.submit(Some(encoder.finish()));
+        self.device.poll(wgpu::Maintain::Wait);
+    }
+
+    /// Copy `src` to a staging buffer, map it and return its contents as a `Vec<T>`.
+    /// Does the staging-buffer copy + `map_async` + oneshot-channel + poll sequence once.
+    pub async fn read_back<T: Pod + Zeroable>(&self, src: &TypedBuffer<T>) -> Result<Vec<T>, Box<dyn Error>> {
+        let byte_size = src.byte_size();
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("compute-context-staging"),
+            size: byte_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("compute-context-readback-encoder"),
+        });
+        encoder.copy_buffer_to_buffer(&src.buffer, 0, &staging, 0, byte_size);
+        self.queue.submit(Some(encoder.finish()));
+        self.device.poll(wgpu::Maintain::Wait);
+
+        let slice = staging.slice(..);
+        let (sender, receiver) = oneshot_channel();
+        slice.map_async(wgpu::MapMode::Read, move |res| {
+            sender.send(res).ok();
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver.receive().await.ok_or("map callback failed")??;
+
+        let data = slice.get_mapped_range();
+        let result: Vec<T> = cast_slice(&data).to_vec();
+        drop(data);
+        staging.unmap();
+
+        Ok(result)
+    }
+}
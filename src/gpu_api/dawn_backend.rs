@@ -0,0 +1,12 @@
+// src/gpu_api/dawn_backend.rs
+//
+// `backend-dawn`: placeholder slot for a future FFI-backed Dawn implementation of the
+// `gpu_api` handles. Not implemented yet — enabling this feature is a build error until
+// the FFI bindings land; `backend-wgpu` remains the default.
+
+compile_error!("backend-dawn is not implemented yet; build with the default backend-wgpu feature");
+
+/// Name reported by `get_info()` prints so users can tell which backend a run used.
+pub fn backend_name() -> &'static str {
+    "dawn"
+}
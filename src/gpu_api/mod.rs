@@ -0,0 +1,25 @@
+// src/gpu_api/mod.rs
+//
+// Backend-agnostic type layer for the WebGPU handles
+// (`Instance`, `Adapter`, `Device`, `Queue`, `Buffer`, `ComputePipeline`, `BindGroup`).
+// `introduction`, `parallel_sums_64` and `sort` name these `gpu_api::` types in their
+// signatures instead of `wgpu::` ones. The one exception is `parallel_sums_64::init_wgpu`,
+// which creates its instance through `wgpu::Instance` directly.
+// Data-only types (buffer usage flags, descriptors, pass/encoder builders) are passed
+// straight through from `wgpu` regardless of backend, since they describe work rather
+// than identify which backend runs it.
+//
+// Exactly one of `backend-wgpu` (default) / `backend-dawn` must be enabled.
+
+#[cfg(feature = "backend-wgpu")]
+mod wgpu_backend;
+#[cfg(feature = "backend-wgpu")]
+pub use wgpu_backend::*;
+
+#[cfg(feature = "backend-dawn")]
+mod dawn_backend;
+#[cfg(feature = "backend-dawn")]
+pub use dawn_backend::*;
+
+#[cfg(not(any(feature = "backend-wgpu", feature = "backend-dawn")))]
+compile_error!("enable exactly one of the `backend-wgpu` or `backend-dawn` features");
@@ -0,0 +1,17 @@
+// src/gpu_api/wgpu_backend.rs
+//
+// `backend-wgpu` (default): the `gpu_api` handles are plain aliases for the `wgpu` crate's
+// own types, so this backend costs nothing beyond the indirection of the names.
+
+pub type Instance = wgpu::Instance;
+pub type Adapter = wgpu::Adapter;
+pub type Device = wgpu::Device;
+pub type Queue = wgpu::Queue;
+pub type Buffer = wgpu::Buffer;
+pub type ComputePipeline = wgpu::ComputePipeline;
+pub type BindGroup = wgpu::BindGroup;
+
+/// Name reported by `get_info()` prints so users can tell which backend a run used.
+pub fn backend_name() -> &'static str {
+    "wgpu"
+}
@@ -1,8 +1,12 @@
 // use pollster::FutureExt;
 use pollster::block_on;
 
+mod compute_context;
+mod gpu_api;
 mod introduction;
 mod parallel_sums_64;
+#[cfg(feature = "sort")]
+mod sort;
 
 fn main() {
     env_logger::init();
@@ -16,10 +20,22 @@ fn main() {
 
     block_on(parallel_sums_64::run()).unwrap();
 
-    // #[cfg(feature="sort")]
-    // compute::sort::run().block_on().unwrap();
-
-
+    // Multi-stream demo: reduce a couple of sample jobs concurrently across worker
+    // threads sharing one Device/Queue, for comparison against the single-threaded path
+    // above.
+    let jobs = vec![
+        (1..=8192u32).collect::<Vec<_>>(),
+        (1..=8192u32).map(|v| v * 2).collect::<Vec<_>>(),
+    ];
+    for result in parallel_sums_64::run_multithreaded(2, jobs) {
+        println!(
+            "[run_multithreaded] job {}: total = {}, elapsed = {:.3?}",
+            result.job_index, result.total, result.elapsed
+        );
+    }
+
+    #[cfg(feature = "sort")]
+    block_on(sort::run()).unwrap();
 }
 
 
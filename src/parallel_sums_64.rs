@@ -1,11 +1,17 @@
 // src/parallel_sums_64.rs
 use std::error::Error;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
 use std::time::Instant;
 
 use futures_intrusive::channel::shared::oneshot_channel;
-use bytemuck::cast_slice;
+use bytemuck::{bytes_of, cast_slice};
+use pollster::block_on;
 use wgpu::util::DeviceExt;
 
+use crate::compute_context::{ComputeContext, TypedBuffer};
+
 
 
 // 1. Host gives array = [1,2,3,...,16384]
@@ -24,8 +30,9 @@ use wgpu::util::DeviceExt;
 
 // ====================================================================================
 
-/// Initialize WebGPU and return (adapter, device, queue).
-pub async fn init_wgpu() -> Result<(wgpu::Adapter, wgpu::Device, wgpu::Queue), Box<dyn Error>> {
+/// Initialize WebGPU and return (adapter, device, queue), typed as the `gpu_api` handles.
+/// The instance itself is still created through `wgpu::Instance` directly.
+pub async fn init_wgpu() -> Result<(crate::gpu_api::Adapter, crate::gpu_api::Device, crate::gpu_api::Queue), Box<dyn Error>> {
     let instance = wgpu::Instance::default();
     let adapter = instance
         .request_adapter(&wgpu::RequestAdapterOptions {
@@ -48,64 +55,99 @@ pub async fn init_wgpu() -> Result<(wgpu::Adapter, wgpu::Device, wgpu::Queue), B
     Ok((adapter, device, queue))
 }
 
-/// Create input, partials and staging buffers.
+/// Build the `ComputeContext` for `parallel_sums_64.wgsl`: binding 0 is the read-only
+/// input, binding 1 the read-write partials.
+pub fn create_compute_context(device: crate::gpu_api::Device, queue: crate::gpu_api::Queue) -> ComputeContext {
+    let layout_entries = [
+        wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+        wgpu::BindGroupLayoutEntry {
+            binding: 1,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+    ];
+    ComputeContext::new(
+        device,
+        queue,
+        wgpu::include_wgsl!("../shaders/parallel_sums_64.wgsl"),
+        &layout_entries,
+        "main",
+    )
+}
+
+/// Upload the input and allocate the partials buffer.
 /// - `input` is a slice of u32 values (host).
 /// - `num_groups` is how many partial sums (one per workgroup).
-pub fn create_buffers(
-    device: &wgpu::Device,
-    input: &[u32],
-    num_groups: u32,
-) -> (wgpu::Buffer, wgpu::Buffer, wgpu::Buffer) {
-    let input_bytes = cast_slice(input);
-    let input_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-        label: Some("input"),
-        contents: input_bytes,
-        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-    });
+pub fn create_buffers(ctx: &ComputeContext, input: &[u32], num_groups: u32) -> (TypedBuffer<u32>, TypedBuffer<u32>) {
+    let input_buffer = ctx.upload(input);
+    let partials_buffer = ctx.allocate::<u32>(num_groups as usize);
+    (input_buffer, partials_buffer)
+}
 
-    let partials_size = (num_groups as u64) * std::mem::size_of::<u32>() as u64;
-    let partials_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-        label: Some("partials"),
-        size: partials_size,
-        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
-        mapped_at_creation: false,
-    });
+/// Build the bind group for `parallel_sums_64.wgsl`, binding 0 to `input_buffer` and
+/// binding 1 to `partials_buffer`.
+pub fn create_pipeline_and_bindgroup(
+    ctx: &ComputeContext,
+    input_buffer: &TypedBuffer<u32>,
+    partials_buffer: &TypedBuffer<u32>,
+) -> crate::gpu_api::BindGroup {
+    ctx.bind(&[input_buffer, partials_buffer])
+}
 
-    let staging = device.create_buffer(&wgpu::BufferDescriptor {
-        label: Some("staging"),
-        size: partials_size,
-        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
-        mapped_at_creation: false,
-    });
+/// Dispatch the compute shader and read back the partials.
+/// This function performs the submit + wait and reads back the CPU-visible data.
+pub async fn dispatch_and_read_partials(
+    ctx: &ComputeContext,
+    bind_group: &crate::gpu_api::BindGroup,
+    partials_buffer: &TypedBuffer<u32>,
+    num_groups: u32,
+) -> Result<(Vec<u32>, std::time::Duration, std::time::Duration), Box<dyn Error>> {
+    let gpu_start = Instant::now();
+    ctx.dispatch(bind_group, (num_groups, 1, 1));
+    let gpu_elapsed = gpu_start.elapsed();
 
-    (input_buffer, partials_buffer, staging)
-}
+    let full_start = Instant::now();
+    let partials = ctx.read_back(partials_buffer).await?;
+    let full_elapsed = full_start.elapsed();
 
-/// Create the compute pipeline and bind group for `parallel_sums_64.wgsl`.
-/// Returns (pipeline, bind_group).
-pub fn create_pipeline_and_bindgroup(
-    device: &wgpu::Device,
-    input_buffer: &wgpu::Buffer,
-    partials_buffer: &wgpu::Buffer,
-) -> (wgpu::ComputePipeline, wgpu::BindGroup) {
-    // compile-time include keeps path issues away
-    let module = device.create_shader_module(wgpu::include_wgsl!("../shaders/parallel_sums_64.wgsl"));
+    Ok((partials, gpu_elapsed, full_elapsed))
+}
 
+/// Build the pipeline/bind group for `indirect_setup.wgsl`, which writes `ceil(count/64)`
+/// (clamped to at least 1) into `indirect_args[0]` and `1` into `indirect_args[1..3]`.
+fn create_indirect_setup(
+    device: &crate::gpu_api::Device,
+    indirect_buffer: &crate::gpu_api::Buffer,
+    count: u32,
+) -> (crate::gpu_api::ComputePipeline, crate::gpu_api::BindGroup) {
+    let module = device.create_shader_module(wgpu::include_wgsl!("../shaders/indirect_setup.wgsl"));
     let bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-        label: Some("bgl"),
+        label: Some("indirect-setup-bgl"),
         entries: &[
-            // binding 0: input (read-only storage)
             wgpu::BindGroupLayoutEntry {
                 binding: 0,
                 visibility: wgpu::ShaderStages::COMPUTE,
                 ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    ty: wgpu::BufferBindingType::Uniform,
                     has_dynamic_offset: false,
                     min_binding_size: None,
                 },
                 count: None,
             },
-            // binding 1: partials (read-write storage)
             wgpu::BindGroupLayoutEntry {
                 binding: 1,
                 visibility: wgpu::ShaderStages::COMPUTE,
@@ -118,74 +160,249 @@ pub fn create_pipeline_and_bindgroup(
             },
         ],
     });
-
-    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-        label: Some("pipeline_layout"),
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("indirect-setup-pipeline_layout"),
         bind_group_layouts: &[&bgl],
         push_constant_ranges: &[],
     });
-
     let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-        label: Some("compute_pipeline"),
-        layout: Some(&pipeline_layout),
+        label: Some("indirect-setup-pipeline"),
+        layout: Some(&layout),
         module: &module,
         entry_point: "main",
     });
 
+    let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("indirect-setup-params"),
+        contents: bytes_of(&count),
+        usage: wgpu::BufferUsages::UNIFORM,
+    });
     let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-        label: Some("bind_group"),
+        label: Some("indirect-setup-bind_group"),
         layout: &bgl,
         entries: &[
-            wgpu::BindGroupEntry {
-                binding: 0,
-                resource: input_buffer.as_entire_binding(),
-            },
-            wgpu::BindGroupEntry {
-                binding: 1,
-                resource: partials_buffer.as_entire_binding(),
-            },
+            wgpu::BindGroupEntry { binding: 0, resource: params_buffer.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 1, resource: indirect_buffer.as_entire_binding() },
         ],
     });
 
     (pipeline, bind_group)
 }
 
-/// Dispatch the compute shader, copy partials to staging, map and return a Vec<u32> of partials.
-/// This function performs the submit + wait and reads back the CPU-visible data.
-pub async fn dispatch_and_read_partials(
-    device: &wgpu::Device,
-    queue: &wgpu::Queue,
-    pipeline: &wgpu::ComputePipeline,
-    bind_group: &wgpu::BindGroup,
-    partials_buffer: &wgpu::Buffer,
-    staging: &wgpu::Buffer,
-    num_groups: u32,
+/// Indirect-dispatch variant of `dispatch_and_read_partials`: a "setup" compute pass
+/// writes the main dispatch's `[x, y, z]` workgroup counts into a GPU-side indirect
+/// argument buffer (derived on-GPU from `count`, via `indirect_setup.wgsl`), and the main
+/// dispatch (against `ctx`'s cached pipeline) reads them back with
+/// `dispatch_workgroups_indirect` instead of the host computing `num_groups` itself. Both
+/// passes are encoded into one encoder so the setup write is visible before the indirect
+/// dispatch reads it. Readback reuses `ComputeContext::read_back`.
+///
+/// Requires `wgpu::DownlevelFlags::INDIRECT_EXECUTION` on the adapter.
+pub async fn dispatch_and_read_partials_indirect(
+    ctx: &ComputeContext,
+    bind_group: &crate::gpu_api::BindGroup,
+    partials_buffer: &TypedBuffer<u32>,
+    count: u32,
 ) -> Result<(Vec<u32>, std::time::Duration, std::time::Duration), Box<dyn Error>> {
-    // Encode compute pass and copy to staging
+    let device = ctx.device();
+    let queue = ctx.queue();
+
+    let indirect_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("indirect-args"),
+        size: 3 * std::mem::size_of::<u32>() as u64,
+        usage: wgpu::BufferUsages::INDIRECT | wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let (setup_pipeline, setup_bind_group) = create_indirect_setup(device, &indirect_buffer, count);
+
     let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-        label: Some("compute-encoder"),
+        label: Some("compute-encoder-indirect"),
     });
 
     {
         let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some("compute-pass"),
+            label: Some("indirect-setup-pass"),
         });
-        cpass.set_pipeline(pipeline);
-        cpass.set_bind_group(0, bind_group, &[]);
-        cpass.dispatch_workgroups(num_groups, 1, 1);
+        cpass.set_pipeline(&setup_pipeline);
+        cpass.set_bind_group(0, &setup_bind_group, &[]);
+        cpass.dispatch_workgroups(1, 1, 1);
     }
 
-    let partials_bytes = (num_groups as u64) * std::mem::size_of::<u32>() as u64;
-    encoder.copy_buffer_to_buffer(partials_buffer, 0, staging, 0, partials_bytes);
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("compute-pass-indirect"),
+        });
+        cpass.set_pipeline(ctx.pipeline());
+        cpass.set_bind_group(0, bind_group, &[]);
+        cpass.dispatch_workgroups_indirect(&indirect_buffer, 0);
+    }
 
-    // Submit and wait for compute
     let gpu_start = Instant::now();
     queue.submit(Some(encoder.finish()));
     device.poll(wgpu::Maintain::Wait);
     let gpu_elapsed = gpu_start.elapsed();
 
-    // Map staging and read
     let full_start = Instant::now();
+    let partials = ctx.read_back(partials_buffer).await?;
+    let full_elapsed = full_start.elapsed();
+
+    Ok((partials, gpu_elapsed, full_elapsed))
+}
+
+/// Number of 64-wide workgroups needed to cover `len` elements.
+fn ceil_div64(len: u32) -> u32 {
+    (len + 63) / 64
+}
+
+/// Reduce `input` all the way down to a single scalar on the GPU, looping reduction
+/// levels inside one command encoder until one value remains, instead of shipping the
+/// first level's partials to the host and finishing the sum there.
+///
+/// Every level reuses the same pair of ping-pong buffers (sized for the largest level
+/// after the first, `ceil(n/64)`), re-pointing the bind group at `src`/`dst` each pass.
+/// Because every level is its own `begin_compute_pass` within a single encoder, wgpu's
+/// automatic storage-buffer barrier between passes makes each level's writes visible to
+/// the next without any host round-trip.
+pub async fn reduce_full(
+    device: &crate::gpu_api::Device,
+    queue: &crate::gpu_api::Queue,
+    input: &[u32],
+) -> Result<u32, Box<dyn Error>> {
+    let n = input.len() as u32;
+    assert!(n > 0, "reduce_full requires a non-empty input");
+
+    // COPY_SRC is needed alongside buf_a/buf_b's: when n == 1 the per-level loop below
+    // runs zero times and `src` is never reassigned away from `input_buffer`, so the
+    // final copy-to-staging reads directly from it.
+    let input_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("reduce_full-input"),
+        contents: cast_slice(input),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+    });
+
+    // Every level after the first operates on at most `ceil(n/64)` elements, so one
+    // pair of buffers of that size is enough to ping-pong for the rest of the tree.
+    let max_level_len = ceil_div64(n).max(1);
+    let level_size = (max_level_len as u64) * std::mem::size_of::<u32>() as u64;
+    let buf_a = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("reduce_full-a"),
+        size: level_size,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let buf_b = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("reduce_full-b"),
+        size: level_size,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+
+    let module = device.create_shader_module(wgpu::include_wgsl!("../shaders/reduce_full.wgsl"));
+    let bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("reduce_full-bgl"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: false },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ],
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("reduce_full-pipeline_layout"),
+        bind_group_layouts: &[&bgl],
+        push_constant_ranges: &[],
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("reduce_full-pipeline"),
+        layout: Some(&pipeline_layout),
+        module: &module,
+        entry_point: "main",
+    });
+
+    // Precompute every level's element count so each level's (tiny) uniform buffer and
+    // bind group can be built before the single encoder below is recorded.
+    let mut level_lens = vec![n];
+    while *level_lens.last().unwrap() > 1 {
+        level_lens.push(ceil_div64(*level_lens.last().unwrap()));
+    }
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("reduce_full-encoder"),
+    });
+
+    let mut src = &input_buffer;
+    let mut dst = &buf_a;
+    let mut next_dst = &buf_b;
+
+    for &cur_len in &level_lens[..level_lens.len() - 1] {
+        let num_groups = ceil_div64(cur_len).max(1);
+
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("reduce_full-params"),
+            contents: bytes_of(&cur_len),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("reduce_full-bind_group"),
+            layout: &bgl,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: src.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: dst.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: params_buffer.as_entire_binding() },
+            ],
+        });
+
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("reduce_full-pass"),
+            });
+            cpass.set_pipeline(&pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
+            cpass.dispatch_workgroups(num_groups, 1, 1);
+        }
+
+        src = dst;
+        std::mem::swap(&mut dst, &mut next_dst);
+    }
+
+    let result_size = std::mem::size_of::<u32>() as u64;
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("reduce_full-staging"),
+        size: result_size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    encoder.copy_buffer_to_buffer(src, 0, &staging, 0, result_size);
+
+    queue.submit(Some(encoder.finish()));
+    device.poll(wgpu::Maintain::Wait);
+
     let slice = staging.slice(..);
     let (sender, receiver) = oneshot_channel();
     slice.map_async(wgpu::MapMode::Read, move |v| {
@@ -195,13 +412,102 @@ pub async fn dispatch_and_read_partials(
     receiver.receive().await.ok_or("map callback failed")??;
 
     let data = slice.get_mapped_range();
-    let partials: Vec<u32> = cast_slice(&data).to_vec();
-    let full_elapsed = full_start.elapsed();
-
+    let result = cast_slice::<u8, u32>(&data)[0];
     drop(data);
     staging.unmap();
 
-    Ok((partials, gpu_elapsed, full_elapsed))
+    Ok(result)
+}
+
+/// Like `init_wgpu`, but `Arc`-wraps the device and queue so several worker threads can
+/// share one GPU connection. `gpu_api::Device`/`gpu_api::Queue` are `Send + Sync`, so each
+/// thread can record and submit its own command buffer against the same handles.
+pub async fn init_wgpu_shared(
+) -> Result<(crate::gpu_api::Adapter, Arc<crate::gpu_api::Device>, Arc<crate::gpu_api::Queue>), Box<dyn Error>> {
+    let (adapter, device, queue) = init_wgpu().await?;
+    Ok((adapter, Arc::new(device), Arc::new(queue)))
+}
+
+/// Result of reducing one job's input on a worker thread, returned by `run_multithreaded`.
+pub struct JobResult {
+    pub job_index: usize,
+    pub partials: Vec<u32>,
+    pub total: u64,
+    pub elapsed: std::time::Duration,
+}
+
+/// Build buffers/pipeline/bind group for one job's input and dispatch+read it back.
+/// This is exactly the `create_buffers` / `create_pipeline_and_bindgroup` /
+/// `dispatch_and_read_partials` sequence `run` uses, pulled out so each worker thread in
+/// `run_multithreaded` can compose it against the shared device/queue.
+async fn run_job(
+    device: &crate::gpu_api::Device,
+    queue: &crate::gpu_api::Queue,
+    input: &[u32],
+) -> Result<(Vec<u32>, u64), Box<dyn Error>> {
+    let elements_per_group: u32 = 64;
+    let num_groups = ((input.len() as u32) + elements_per_group - 1) / elements_per_group;
+
+    let ctx = create_compute_context(device.clone(), queue.clone());
+    let (input_buffer, partials_buffer) = create_buffers(&ctx, input, num_groups);
+    let bind_group = create_pipeline_and_bindgroup(&ctx, &input_buffer, &partials_buffer);
+    let (partials, _gpu_elapsed, _full_elapsed) =
+        dispatch_and_read_partials(&ctx, &bind_group, &partials_buffer, num_groups).await?;
+
+    let total: u64 = partials.iter().map(|&v| v as u64).sum();
+    Ok((partials, total))
+}
+
+/// Run `jobs` concurrently across `thread_count` CPU worker threads, each building its
+/// own encoder/bind group and submitting an independent reduction against a shared
+/// `Arc<gpu_api::Device>`/`Arc<gpu_api::Queue>`, with readback happening per-thread. Jobs are
+/// distributed round-robin across the worker threads. Prints per-thread timing so users
+/// can compare against the single-threaded path in `run`.
+pub fn run_multithreaded(thread_count: usize, jobs: Vec<Vec<u32>>) -> Vec<JobResult> {
+    let (adapter, device, queue) = block_on(init_wgpu_shared()).expect("failed to init wgpu");
+    println!("Adapter: {:?}", adapter.get_info());
+
+    let thread_count = thread_count.max(1);
+    let mut buckets: Vec<Vec<(usize, Vec<u32>)>> = (0..thread_count).map(|_| Vec::new()).collect();
+    for (job_index, input) in jobs.into_iter().enumerate() {
+        buckets[job_index % thread_count].push((job_index, input));
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let handles: Vec<_> = buckets
+        .into_iter()
+        .enumerate()
+        .filter(|(_, bucket)| !bucket.is_empty())
+        .map(|(worker_index, bucket)| {
+            let device = Arc::clone(&device);
+            let queue = Arc::clone(&queue);
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for (job_index, input) in bucket {
+                    let thread_start = Instant::now();
+                    match block_on(run_job(&device, &queue, &input)) {
+                        Ok((partials, total)) => {
+                            let elapsed = thread_start.elapsed();
+                            println!("[worker {worker_index}] job {job_index}: {elapsed:.3?}");
+                            tx.send(JobResult { job_index, partials, total, elapsed }).ok();
+                        }
+                        Err(e) => {
+                            eprintln!("[worker {worker_index}] job {job_index} failed: {e}");
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+
+    drop(tx);
+    for handle in handles {
+        handle.join().expect("worker thread panicked");
+    }
+
+    let mut results: Vec<JobResult> = rx.into_iter().collect();
+    results.sort_by_key(|r| r.job_index);
+    results
 }
 
 /// Thin orchestrator: uses the helpers above to run the 64-thread reduction, sum partials and print results.
@@ -217,25 +523,17 @@ pub async fn run() -> Result<(), Box<dyn Error>> {
 
     // init GPU
     let (adapter, device, queue) = init_wgpu().await?;
+    println!("Backend: {}", crate::gpu_api::backend_name());
     println!("Adapter: {:?}", adapter.get_info());
 
-    // create buffers
-    let (input_buffer, partials_buffer, staging) = create_buffers(&device, &input_data, num_groups);
-
-    // pipeline + bind group
-    let (pipeline, bind_group) = create_pipeline_and_bindgroup(&device, &input_buffer, &partials_buffer);
+    // context + buffers + bind group
+    let ctx = create_compute_context(device.clone(), queue.clone());
+    let (input_buffer, partials_buffer) = create_buffers(&ctx, &input_data, num_groups);
+    let bind_group = create_pipeline_and_bindgroup(&ctx, &input_buffer, &partials_buffer);
 
     // dispatch and read partials
-    let (partials, gpu_elapsed, full_elapsed) = dispatch_and_read_partials(
-        &device,
-        &queue,
-        &pipeline,
-        &bind_group,
-        &partials_buffer,
-        &staging,
-        num_groups,
-    )
-    .await?;
+    let (partials, gpu_elapsed, full_elapsed) =
+        dispatch_and_read_partials(&ctx, &bind_group, &partials_buffer, num_groups).await?;
 
     // sum and verify on host
     let gpu_total: u64 = partials.iter().map(|&v| v as u64).sum();
@@ -249,5 +547,24 @@ pub async fn run() -> Result<(), Box<dyn Error>> {
     println!("Expected answer = {}", expected);
     println!("Match: {}", gpu_total == expected);
 
+    // Full on-GPU tree reduction: keeps reducing on the GPU until one scalar
+    // remains, so the CPU never pays for the final summation pass above.
+    let full_start = Instant::now();
+    let gpu_full_total = reduce_full(&device, &queue, &input_data).await?;
+    let full_elapsed = full_start.elapsed();
+    println!("GPU full-reduction time: {:.3?}", full_elapsed);
+    println!("Total from GPU full reduction = {}", gpu_full_total);
+    println!("Match (full reduction): {}", gpu_full_total as u64 == expected);
+
+    // Same first-level reduction, but the workgroup count for the dispatch is computed
+    // on-GPU and read via an indirect argument buffer instead of on the host.
+    let (partials_indirect, gpu_elapsed_indirect, full_elapsed_indirect) =
+        dispatch_and_read_partials_indirect(&ctx, &bind_group, &partials_buffer, n).await?;
+    let indirect_total: u64 = partials_indirect.iter().map(|&v| v as u64).sum();
+    println!("GPU dispatch+execute time (indirect): {:.3?}", gpu_elapsed_indirect);
+    println!("Full roundtrip time (indirect): {:.3?}", full_elapsed_indirect);
+    println!("Total from GPU partials (indirect) = {}", indirect_total);
+    println!("Match (indirect): {}", indirect_total == expected);
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,324 @@
+// src/sort.rs
+//
+// GPU merge sort, following the same buffer/pipeline/staging-readback pattern as
+// `parallel_sums_64.rs` (it records its own buffers, pipelines and readback rather than
+// calling that module's helpers), but as a three-stage pipeline instead of a single
+// reduction kernel:
+//   1. block sort   - one workgroup sorts each 64-element block in shared memory.
+//   2. merge offsets - for each merge width, binary-search the merge-path diagonal
+//                       split points between adjacent sorted runs.
+//   3. merge blocks  - each invocation merges its BLOCK_LEN-wide slice of a run pair
+//                       using the precomputed offsets, ping-ponging between two buffers
+//                       until one sorted run covers the whole (padded) array.
+//
+// Inputs whose length is not a multiple of `BLOCK_LEN` are padded up to one with
+// `u32::MAX` sentinels, which always sort to the end and are trimmed off the final result.
+
+use std::error::Error;
+
+use bytemuck::{bytes_of, cast_slice, Pod, Zeroable};
+use futures_intrusive::channel::shared::oneshot_channel;
+use wgpu::util::DeviceExt;
+
+const BLOCK_LEN: u32 = 64;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct MergeParams {
+    run_len: u32,
+    n: u32,
+    num_offsets: u32,
+    _padding: u32,
+}
+
+/// Round `n` up to the next multiple of `BLOCK_LEN`.
+fn padded_len(n: u32) -> u32 {
+    ((n + BLOCK_LEN - 1) / BLOCK_LEN) * BLOCK_LEN
+}
+
+/// Sort `data` entirely on the GPU. See the module docs above for the three-stage
+/// pipeline this drives.
+pub async fn sort(
+    device: &crate::gpu_api::Device,
+    queue: &crate::gpu_api::Queue,
+    data: &[u32],
+) -> Result<Vec<u32>, Box<dyn Error>> {
+    let n = data.len() as u32;
+    if n <= 1 {
+        return Ok(data.to_vec());
+    }
+    let padded_n = padded_len(n);
+
+    let mut padded_data = data.to_vec();
+    padded_data.resize(padded_n as usize, u32::MAX);
+
+    let byte_size = (padded_n as u64) * std::mem::size_of::<u32>() as u64;
+
+    let mut buf_a = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("sort-a"),
+        contents: cast_slice(&padded_data),
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+    });
+    let mut buf_b = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("sort-b"),
+        size: byte_size,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+
+    block_sort_pass(device, queue, &buf_a, padded_n);
+
+    // Repeatedly merge adjacent sorted runs of width `run_len` into runs of width
+    // `2 * run_len`, doubling `run_len` until one run covers the whole padded array.
+    let mut run_len = BLOCK_LEN;
+    while run_len < padded_n {
+        merge_pass(device, queue, &buf_a, &buf_b, run_len, padded_n);
+        std::mem::swap(&mut buf_a, &mut buf_b);
+        run_len *= 2;
+    }
+
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("sort-staging"),
+        size: byte_size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("sort-readback-encoder"),
+    });
+    encoder.copy_buffer_to_buffer(&buf_a, 0, &staging, 0, byte_size);
+    queue.submit(Some(encoder.finish()));
+    device.poll(wgpu::Maintain::Wait);
+
+    let slice = staging.slice(..);
+    let (sender, receiver) = oneshot_channel();
+    slice.map_async(wgpu::MapMode::Read, move |v| {
+        sender.send(v).ok();
+    });
+    device.poll(wgpu::Maintain::Wait);
+    receiver.receive().await.ok_or("map callback failed")??;
+
+    let mapped = slice.get_mapped_range();
+    let sorted: Vec<u32> = cast_slice::<u8, u32>(&mapped)[..n as usize].to_vec();
+    drop(mapped);
+    staging.unmap();
+
+    Ok(sorted)
+}
+
+/// Stage 1: sort every `BLOCK_LEN`-element block of `buf` in place.
+fn block_sort_pass(
+    device: &crate::gpu_api::Device,
+    queue: &crate::gpu_api::Queue,
+    buf: &crate::gpu_api::Buffer,
+    padded_n: u32,
+) {
+    let module = device.create_shader_module(wgpu::include_wgsl!("../shaders/sort_block.wgsl"));
+    let bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("sort-block-bgl"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("sort-block-pipeline_layout"),
+        bind_group_layouts: &[&bgl],
+        push_constant_ranges: &[],
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("sort-block-pipeline"),
+        layout: Some(&layout),
+        module: &module,
+        entry_point: "main",
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("sort-block-bind_group"),
+        layout: &bgl,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: buf.as_entire_binding(),
+        }],
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("sort-block-encoder"),
+    });
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("sort-block-pass"),
+        });
+        cpass.set_pipeline(&pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.dispatch_workgroups(padded_n / BLOCK_LEN, 1, 1);
+    }
+    queue.submit(Some(encoder.finish()));
+    device.poll(wgpu::Maintain::Wait);
+}
+
+/// Stages 2 + 3 for one merge width: find the merge-path offsets between every pair of
+/// adjacent `run_len`-wide runs in `src`, then merge each pair into `dst`.
+fn merge_pass(
+    device: &crate::gpu_api::Device,
+    queue: &crate::gpu_api::Queue,
+    src: &crate::gpu_api::Buffer,
+    dst: &crate::gpu_api::Buffer,
+    run_len: u32,
+    padded_n: u32,
+) {
+    let num_pairs = (padded_n + run_len * 2 - 1) / (run_len * 2);
+    let offsets_per_pair = (run_len * 2) / BLOCK_LEN;
+    let num_offsets = num_pairs * offsets_per_pair;
+
+    let offsets_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("sort-offsets"),
+        size: (num_offsets as u64) * std::mem::size_of::<u32>() as u64,
+        usage: wgpu::BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    });
+    let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("sort-merge-params"),
+        contents: bytes_of(&MergeParams { run_len, n: padded_n, num_offsets, _padding: 0 }),
+        usage: wgpu::BufferUsages::UNIFORM,
+    });
+
+    let offsets_module = device.create_shader_module(wgpu::include_wgsl!("../shaders/sort_merge_offsets.wgsl"));
+    let offsets_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("sort-offsets-bgl"),
+        entries: &[
+            storage_entry(0, true),
+            storage_entry(1, false),
+            uniform_entry(2),
+        ],
+    });
+    let offsets_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("sort-offsets-pipeline_layout"),
+        bind_group_layouts: &[&offsets_bgl],
+        push_constant_ranges: &[],
+    });
+    let offsets_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("sort-offsets-pipeline"),
+        layout: Some(&offsets_layout),
+        module: &offsets_module,
+        entry_point: "main",
+    });
+    let offsets_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("sort-offsets-bind_group"),
+        layout: &offsets_bgl,
+        entries: &[
+            wgpu::BindGroupEntry { binding: 0, resource: src.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 1, resource: offsets_buffer.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 2, resource: params_buffer.as_entire_binding() },
+        ],
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("sort-offsets-encoder"),
+    });
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("sort-offsets-pass"),
+        });
+        cpass.set_pipeline(&offsets_pipeline);
+        cpass.set_bind_group(0, &offsets_bind_group, &[]);
+        cpass.dispatch_workgroups(((num_offsets + BLOCK_LEN - 1) / BLOCK_LEN).max(1), 1, 1);
+    }
+    queue.submit(Some(encoder.finish()));
+    device.poll(wgpu::Maintain::Wait);
+
+    let merge_module = device.create_shader_module(wgpu::include_wgsl!("../shaders/sort_merge.wgsl"));
+    let merge_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("sort-merge-bgl"),
+        entries: &[
+            storage_entry(0, true),
+            storage_entry(1, false),
+            storage_entry(2, true),
+            uniform_entry(3),
+        ],
+    });
+    let merge_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("sort-merge-pipeline_layout"),
+        bind_group_layouts: &[&merge_bgl],
+        push_constant_ranges: &[],
+    });
+    let merge_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("sort-merge-pipeline"),
+        layout: Some(&merge_layout),
+        module: &merge_module,
+        entry_point: "main",
+    });
+    let merge_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("sort-merge-bind_group"),
+        layout: &merge_bgl,
+        entries: &[
+            wgpu::BindGroupEntry { binding: 0, resource: src.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 1, resource: dst.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 2, resource: offsets_buffer.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 3, resource: params_buffer.as_entire_binding() },
+        ],
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("sort-merge-encoder"),
+    });
+    {
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("sort-merge-pass"),
+        });
+        cpass.set_pipeline(&merge_pipeline);
+        cpass.set_bind_group(0, &merge_bind_group, &[]);
+        cpass.dispatch_workgroups(((num_offsets + BLOCK_LEN - 1) / BLOCK_LEN).max(1), 1, 1);
+    }
+    queue.submit(Some(encoder.finish()));
+    device.poll(wgpu::Maintain::Wait);
+}
+
+fn storage_entry(binding: u32, read_only: bool) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn uniform_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+/// Thin orchestrator mirroring `parallel_sums_64::run`: sorts a sample array on the GPU
+/// and verifies it against the host's own sort.
+pub async fn run() -> Result<(), Box<dyn Error>> {
+    let (adapter, device, queue) = crate::parallel_sums_64::init_wgpu().await?;
+    println!("Adapter: {:?}", adapter.get_info());
+
+    let input_data: Vec<u32> = (0..10_000u32).rev().collect();
+    let mut expected = input_data.clone();
+    expected.sort_unstable();
+
+    let sorted = sort(&device, &queue, &input_data).await?;
+
+    println!("First 16 sorted values = {:?}", &sorted[..16.min(sorted.len())]);
+    println!("Match: {}", sorted == expected);
+
+    Ok(())
+}